@@ -0,0 +1,137 @@
+// Per-pixel color mapping for the Mandelbrot renderer. Cycle through
+// ColorScheme variants with the `c` key.
+
+use ratatui::style::Color;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sample {
+    pub iterations: u16,
+    pub norm_sqr: f64,
+    pub dz_norm_sqr: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    Histogram,
+    SmoothBlue,
+    FireGradient,
+    HsvCycle,
+    DistanceEstimate,
+}
+
+impl ColorScheme {
+    const ALL: [ColorScheme; 5] = [
+        ColorScheme::Histogram,
+        ColorScheme::SmoothBlue,
+        ColorScheme::FireGradient,
+        ColorScheme::HsvCycle,
+        ColorScheme::DistanceEstimate,
+    ];
+
+    // Histogram keeps the traditional bailout radius of 2; the other
+    // schemes need a much larger radius so their post-escape math stays
+    // well behaved right after escape.
+    pub fn bailout_norm_sqr(self) -> f64 {
+        match self {
+            ColorScheme::Histogram => 4.0,
+            ColorScheme::SmoothBlue
+            | ColorScheme::FireGradient
+            | ColorScheme::HsvCycle
+            | ColorScheme::DistanceEstimate => 256.0,
+        }
+    }
+
+    pub fn needs_derivative(self) -> bool {
+        self == ColorScheme::DistanceEstimate
+    }
+
+    pub fn next(self) -> ColorScheme {
+        let index = Self::ALL.iter().position(|&scheme| scheme == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+// mu = n + 1 - ln(ln(|z|)) / ln(2), which removes the banding a plain
+// integer escape count produces.
+pub fn smooth_iteration_count(sample: Sample) -> f64 {
+    let ln_z = 0.5 * sample.norm_sqr.ln();
+    sample.iterations as f64 + 1.0 - (ln_z.ln() / std::f64::consts::LN_2)
+}
+
+// Points still inside the set should be colored black by the caller
+// instead of calling this.
+pub fn color_for_mu(mu: f64, scheme: ColorScheme) -> Color {
+    // Repeats every PERIOD iterations so deep, detailed regions keep
+    // showing contrast instead of flattening out to one color.
+    const PERIOD: f64 = 32.0;
+    let t = (mu / PERIOD).rem_euclid(1.0);
+    match scheme {
+        ColorScheme::Histogram => unreachable!("Histogram is colored by histogram equalization"),
+        ColorScheme::SmoothBlue => Color::Rgb(0, 0, (t * 255.0).round() as u8),
+        ColorScheme::FireGradient => fire_gradient(t),
+        ColorScheme::HsvCycle => hsv_to_rgb(t * 360.0, 1.0, 1.0),
+        ColorScheme::DistanceEstimate => {
+            unreachable!("DistanceEstimate is colored by color_for_distance")
+        }
+    }
+}
+
+// distance = |z| * ln(|z|) / |dz|, converted to screen units by dividing
+// by the per-pixel step size.
+pub fn color_for_distance(sample: Sample, max_iterations: u16, pixel_step: f64) -> Color {
+    if sample.iterations == max_iterations || sample.dz_norm_sqr == 0.0 {
+        return Color::Rgb(0, 0, 0);
+    }
+
+    let z_norm = sample.norm_sqr.sqrt();
+    let dz_norm = sample.dz_norm_sqr.sqrt();
+    let distance = z_norm * z_norm.ln() / dz_norm;
+    let screen_distance = (distance / pixel_step).clamp(0.0, 1.0);
+    let shade = (screen_distance * 255.0).round() as u8;
+    Color::Rgb(shade, shade, shade)
+}
+
+pub fn to_rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        _ => [0, 0, 0],
+    }
+}
+
+fn fire_gradient(t: f64) -> Color {
+    const STOPS: [(f64, (u8, u8, u8)); 5] = [
+        (0.00, (0, 0, 0)),
+        (0.25, (128, 0, 0)),
+        (0.50, (255, 80, 0)),
+        (0.75, (255, 200, 0)),
+        (1.00, (255, 255, 255)),
+    ];
+    for window in STOPS.windows(2) {
+        let (t0, c0) = window[0];
+        let (t1, c1) = window[1];
+        if t <= t1 {
+            let local = (t - t0) / (t1 - t0);
+            let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * local).round() as u8;
+            return Color::Rgb(lerp(c0.0, c1.0), lerp(c0.1, c1.1), lerp(c0.2, c1.2));
+        }
+    }
+    let (_, c) = STOPS[STOPS.len() - 1];
+    Color::Rgb(c.0, c.1, c.2)
+}
+
+fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> Color {
+    let chroma = value * saturation;
+    let h_prime = hue / 60.0;
+    let x = chroma * (1.0 - (h_prime.rem_euclid(2.0) - 1.0).abs());
+    let (r1, g1, b1) = match h_prime as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    let to_u8 = |component: f64| ((component + m) * 255.0).round() as u8;
+    Color::Rgb(to_u8(r1), to_u8(g1), to_u8(b1))
+}
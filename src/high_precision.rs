@@ -0,0 +1,52 @@
+// Arbitrary-precision complex arithmetic, used once the view has zoomed in
+// far enough that f64 no longer has the mantissa bits to distinguish
+// neighbouring points.
+
+use dashu_float::FBig;
+
+#[derive(Clone)]
+pub struct Complex {
+    re: FBig,
+    im: FBig,
+}
+
+impl Complex {
+    pub fn from_f64(re: f64, im: f64, precision_bits: usize) -> Self {
+        Complex {
+            re: FBig::try_from(re)
+                .unwrap()
+                .with_precision(precision_bits)
+                .value(),
+            im: FBig::try_from(im)
+                .unwrap()
+                .with_precision(precision_bits)
+                .value(),
+        }
+    }
+
+    pub fn zero(precision_bits: usize) -> Self {
+        Complex {
+            re: FBig::ZERO.with_precision(precision_bits).value(),
+            im: FBig::ZERO.with_precision(precision_bits).value(),
+        }
+    }
+
+    pub fn norm_sqr_f64(&self) -> f64 {
+        let norm_sqr = &self.re * &self.re + &self.im * &self.im;
+        norm_sqr.to_f64().value()
+    }
+
+    pub fn square_add(&self, c: &Complex) -> Complex {
+        let re = &self.re * &self.re - &self.im * &self.im + &c.re;
+        let im = FBig::try_from(2.0).unwrap() * &self.re * &self.im + &c.im;
+        Complex { re, im }
+    }
+
+    // dz = 2*z*dz + 1, the derivative orbit step color_for_distance needs.
+    pub fn derivative_step(&self, z: &Complex) -> Complex {
+        let two = FBig::try_from(2.0).unwrap();
+        let re = &two * (&z.re * &self.re - &z.im * &self.im) + FBig::ONE;
+        let im = &two * (&z.re * &self.im + &z.im * &self.re);
+        Complex { re, im }
+    }
+}
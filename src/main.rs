@@ -1,15 +1,52 @@
+mod color;
+mod high_precision;
+mod views;
+
+use std::io::stdout;
 use std::iter::zip;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use color::{ColorScheme, Sample};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton,
+    MouseEventKind,
+};
+use crossterm::execute;
 use num_complex::Complex;
-use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Color,
+    widgets::{Paragraph, Widget},
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use views::ViewState;
 
 use ratatui::{DefaultTerminal, Frame};
 
+const INITIAL_STRIDE: u32 = 8;
+
+const BASE_ITERATIONS: f64 = 1000.0;
+const ITERATIONS_PER_DEPTH: f64 = 200.0;
+// f64 runs out of mantissa bits to resolve neighbouring pixels past this
+// zoom depth, so escape_count switches to the high_precision backend.
+const HIGH_PRECISION_DEPTH: f64 = 45.0;
+// Ceiling on the high-precision backend's working precision, so zoom_depth
+// growing without bound can't turn into a multi-gigabyte FBig allocation.
+const MAX_PRECISION_BITS: usize = 4096;
+
+const EXPORT_WIDTH: u32 = 1920;
+const EXPORT_HEIGHT: u32 = 1080;
+
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let terminal = ratatui::init();
+    // Mouse capture failing isn't fatal, and either way the terminal must be
+    // restored below, so don't let `?` skip past that.
+    let _ = execute!(stdout(), EnableMouseCapture);
     let result = App::new().run(terminal);
+    let _ = execute!(stdout(), DisableMouseCapture);
     ratatui::restore();
     result
 }
@@ -17,100 +54,257 @@ fn main() -> color_eyre::Result<()> {
 struct App {
     mandelbrot: Mandelbrot,
     exit: bool,
+    // The fractal widget's area as of the last frame, used to translate
+    // mouse events (in terminal cell coordinates) into the complex plane.
+    fractal_area: Rect,
+    bookmarks: Vec<ViewState>,
+    bookmark_index: usize,
 }
 
 impl App {
     fn new() -> App {
         App {
-            mandelbrot: Mandelbrot::new(10000, -2.0, 1.0, -1.0, 1.0),
+            mandelbrot: Mandelbrot::new(-2.0, 1.0, -1.0, 1.0),
             exit: false,
+            fractal_area: Rect::default(),
+            bookmarks: views::load(),
+            bookmark_index: 0,
         }
     }
 
     fn run(&mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
         while !self.exit {
+            let size = terminal.size()?;
+            let area = Rect::new(0, 0, size.width, size.height);
+            let (fractal_area, _status_area) = Self::layout(area);
+            self.fractal_area = fractal_area;
+            let refining = self.mandelbrot.refine(fractal_area);
             terminal.draw(|frame| self.render(frame))?;
-            self.handle_events()?;
+            self.handle_events(refining)?;
         }
         Ok(())
     }
 
+    // Shared between `run` (which needs the fractal area ahead of `refine`)
+    // and `render`, so the two stay in sync.
+    fn layout(area: Rect) -> (Rect, Rect) {
+        let [fractal_area, status_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        (fractal_area, status_area)
+    }
+
     fn render(&self, frame: &mut Frame) {
-        frame.render_widget(&self.mandelbrot, frame.area());
+        let (fractal_area, status_area) = Self::layout(frame.area());
+        frame.render_widget(&self.mandelbrot, fractal_area);
+        frame.render_widget(Paragraph::new(self.mandelbrot.status_line()), status_area);
     }
 
-    fn handle_events(&mut self) -> color_eyre::Result<()> {
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Press {
-                return Ok(());
-            }
-            let mandelbrot = &mut self.mandelbrot;
-            match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => self.exit = true,
-                KeyCode::Char('+') => mandelbrot.increase_max_iterations(),
-                KeyCode::Char('-') => mandelbrot.decrease_max_iterations(),
-                KeyCode::Char('k') | KeyCode::Up => mandelbrot.pan_up(),
-                KeyCode::Char('j') | KeyCode::Down => mandelbrot.pan_down(),
-                KeyCode::Char('h') | KeyCode::Left => mandelbrot.pan_left(),
-                KeyCode::Char('l') | KeyCode::Right => mandelbrot.pan_right(),
-                KeyCode::Char('z') | KeyCode::PageUp => mandelbrot.zoom_in(),
-                KeyCode::Char('x') | KeyCode::PageDown => mandelbrot.zoom_out(),
-                _ => {}
-            }
+    // While a refinement pass is in flight, poll instead of blocking on
+    // read so `run` keeps drawing refinement frames between keypresses.
+    fn handle_events(&mut self, refining: bool) -> color_eyre::Result<()> {
+        if refining && !event::poll(Duration::from_millis(0))? {
+            return Ok(());
+        }
+        match event::read()? {
+            Event::Key(key) => self.handle_key_event(key)?,
+            Event::Mouse(mouse) => self.handle_mouse_event(mouse),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) -> color_eyre::Result<()> {
+        if key.kind != KeyEventKind::Press {
+            return Ok(());
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.exit = true,
+            KeyCode::Char('+') => self.mandelbrot.increase_max_iterations(),
+            KeyCode::Char('-') => self.mandelbrot.decrease_max_iterations(),
+            KeyCode::Char('k') | KeyCode::Up => self.mandelbrot.pan_up(),
+            KeyCode::Char('j') | KeyCode::Down => self.mandelbrot.pan_down(),
+            KeyCode::Char('h') | KeyCode::Left => self.mandelbrot.pan_left(),
+            KeyCode::Char('l') | KeyCode::Right => self.mandelbrot.pan_right(),
+            KeyCode::Char('z') | KeyCode::PageUp => self.mandelbrot.zoom_in(),
+            KeyCode::Char('x') | KeyCode::PageDown => self.mandelbrot.zoom_out(),
+            KeyCode::Char('c') => self.mandelbrot.cycle_color_scheme(),
+            KeyCode::Char('s') => self.save_bookmark()?,
+            KeyCode::Char('v') => self.cycle_bookmark(),
+            KeyCode::Char('e') => self.export_image()?,
+            _ => {}
         }
         Ok(())
     }
+
+    fn save_bookmark(&mut self) -> color_eyre::Result<()> {
+        self.bookmarks.push(self.mandelbrot.to_view());
+        self.bookmark_index = self.bookmarks.len() - 1;
+        views::save(&self.bookmarks)?;
+        Ok(())
+    }
+
+    fn cycle_bookmark(&mut self) {
+        if self.bookmarks.is_empty() {
+            return;
+        }
+        self.bookmark_index = (self.bookmark_index + 1) % self.bookmarks.len();
+        self.mandelbrot
+            .apply_view(&self.bookmarks[self.bookmark_index]);
+    }
+
+    fn export_image(&self) -> color_eyre::Result<()> {
+        let image = self.mandelbrot.render_to_image(EXPORT_WIDTH, EXPORT_HEIGHT);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        image.save(format!("mandelbrot-{timestamp}.png"))?;
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, mouse: crossterm::event::MouseEvent) {
+        if mouse.column >= self.fractal_area.width || mouse.row >= self.fractal_area.height {
+            return;
+        }
+        let cursor = self
+            .mandelbrot
+            .cell_to_complex(self.fractal_area, mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.mandelbrot.recenter_on(cursor),
+            MouseEventKind::ScrollUp => self.mandelbrot.zoom_at(cursor, 0.9),
+            MouseEventKind::ScrollDown => self.mandelbrot.zoom_at(cursor, 1.1),
+            _ => {}
+        }
+    }
 }
 
-struct Mandelbrot {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Precision {
+    Standard,
+    High,
+}
+
+// Bundles the parameters that describe one compute_pixels pass, since
+// compute_row/compute_pixels need all of them together.
+struct Grid {
+    width: u32,
+    height: u32,
+    stride: u32,
+    x_step: f64,
+    y_step: f64,
     max_iterations: u16,
+    bailout_norm_sqr: f64,
+    track_derivative: bool,
+}
+
+struct Mandelbrot {
+    manual_iterations: Option<u16>,
     x_min: f64,
     x_max: f64,
     y_min: f64,
     y_max: f64,
+    stride: u32,
+    area: Rect,
+    colors: Vec<Color>,
+    color_scheme: ColorScheme,
 }
 
 impl Mandelbrot {
-    fn new(max_iterations: u16, x_min: f64, x_max: f64, y_min: f64, y_max: f64) -> Mandelbrot {
+    fn new(x_min: f64, x_max: f64, y_min: f64, y_max: f64) -> Mandelbrot {
         Mandelbrot {
-            max_iterations,
+            manual_iterations: None,
             x_min,
             x_max,
             y_min,
             y_max,
+            stride: INITIAL_STRIDE,
+            area: Rect::default(),
+            colors: Vec::new(),
+            color_scheme: ColorScheme::Histogram,
         }
     }
 
+    fn mark_dirty(&mut self) {
+        self.stride = INITIAL_STRIDE;
+    }
+
+    // -log2(width): 0 at the initial view, larger the further in we've zoomed.
+    fn zoom_depth(&self) -> f64 {
+        -(self.x_max - self.x_min).log2()
+    }
+
+    fn effective_max_iterations(&self) -> u16 {
+        self.manual_iterations.unwrap_or_else(|| {
+            let depth = self.zoom_depth().max(0.0);
+            (BASE_ITERATIONS + ITERATIONS_PER_DEPTH * depth).min(u16::MAX as f64) as u16
+        })
+    }
+
+    fn precision(&self) -> Precision {
+        if self.zoom_depth() > HIGH_PRECISION_DEPTH {
+            Precision::High
+        } else {
+            Precision::Standard
+        }
+    }
+
+    fn status_line(&self) -> String {
+        let manual = if self.manual_iterations.is_some() {
+            " (manual)"
+        } else {
+            ""
+        };
+        format!(
+            "iterations: {}{manual}  precision: {:?}  zoom depth: {:.1}  palette: {:?}",
+            self.effective_max_iterations(),
+            self.precision(),
+            self.zoom_depth(),
+            self.color_scheme,
+        )
+    }
+
+    fn cycle_color_scheme(&mut self) {
+        self.color_scheme = self.color_scheme.next();
+        self.mark_dirty();
+    }
+
     fn increase_max_iterations(&mut self) {
-        self.max_iterations += 100;
+        self.manual_iterations = Some(self.effective_max_iterations().saturating_add(100));
+        self.mark_dirty();
     }
 
     fn decrease_max_iterations(&mut self) {
-        self.max_iterations = self.max_iterations.checked_sub(100).unwrap_or(100)
+        let current = self.effective_max_iterations();
+        self.manual_iterations = Some(current.checked_sub(100).unwrap_or(100));
+        self.mark_dirty();
     }
 
     fn pan_left(&mut self) {
         let pan = (self.x_max - self.x_min) * 0.1;
         self.x_min -= pan;
         self.x_max -= pan;
+        self.mark_dirty();
     }
 
     fn pan_right(&mut self) {
         let pan = (self.x_max - self.x_min) * 0.1;
         self.x_min += pan;
         self.x_max += pan;
+        self.mark_dirty();
     }
 
     fn pan_up(&mut self) {
         let pan = (self.y_max - self.y_min) * 0.1;
         self.y_min -= pan;
         self.y_max -= pan;
+        self.mark_dirty();
     }
 
     fn pan_down(&mut self) {
         let pan = (self.y_max - self.y_min) * 0.1;
         self.y_min += pan;
         self.y_max += pan;
+        self.mark_dirty();
     }
 
     fn zoom_in(&mut self) {
@@ -122,6 +316,7 @@ impl Mandelbrot {
         self.x_max = x_center + x_range / 2.0;
         self.y_min = y_center - y_range / 2.0;
         self.y_max = y_center + y_range / 2.0;
+        self.mark_dirty();
     }
 
     fn zoom_out(&mut self) {
@@ -133,64 +328,312 @@ impl Mandelbrot {
         self.x_max = x_center + x_range / 2.0;
         self.y_min = y_center - y_range / 2.0;
         self.y_max = y_center + y_range / 2.0;
+        self.mark_dirty();
     }
-}
 
-impl Widget for &Mandelbrot {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    // Accounts for the 2x-height half-block mapping the Widget impl uses
+    // (each cell row covers two vertical pixels).
+    fn cell_to_complex(&self, area: Rect, column: u16, row: u16) -> Complex<f64> {
         let x_step = (self.x_max - self.x_min) / area.width as f64;
         let y_step = (self.y_max - self.y_min) / area.height as f64 / 2.0;
-        let mut pixels = vec![0; (area.width * area.height * 2) as usize];
-        for y in 0..area.height * 2 {
-            for x in 0..area.width {
-                let c = Complex::new(
-                    self.x_min + x as f64 * x_step,
-                    self.y_min + y as f64 * y_step,
-                );
-                let mut z = Complex::new(0.0, 0.0);
-                let mut n = 0;
-
-                while z.norm_sqr() <= 4.0 && n < self.max_iterations {
-                    z = z * z + c;
-                    n += 1;
-                }
-
-                pixels[(y * area.width + x) as usize] = n;
+        let x = self.x_min + (column as f64 + 0.5) * x_step;
+        let y = self.y_min + (row as f64 * 2.0 + 1.0) * y_step;
+        Complex::new(x, y)
+    }
+
+    fn recenter_on(&mut self, c: Complex<f64>) {
+        let x_range = self.x_max - self.x_min;
+        let y_range = self.y_max - self.y_min;
+        self.x_min = c.re - x_range / 2.0;
+        self.x_max = c.re + x_range / 2.0;
+        self.y_min = c.im - y_range / 2.0;
+        self.y_max = c.im + y_range / 2.0;
+        self.mark_dirty();
+    }
+
+    // factor < 1.0 zooms in, > 1.0 zooms out, around `c` rather than the
+    // view center, so that scrolling zooms towards the cursor.
+    fn zoom_at(&mut self, c: Complex<f64>, factor: f64) {
+        let x_range = (self.x_max - self.x_min) * factor;
+        let y_range = (self.y_max - self.y_min) * factor;
+        let x_frac = (c.re - self.x_min) / (self.x_max - self.x_min);
+        let y_frac = (c.im - self.y_min) / (self.y_max - self.y_min);
+        self.x_min = c.re - x_frac * x_range;
+        self.x_max = self.x_min + x_range;
+        self.y_min = c.im - y_frac * y_range;
+        self.y_max = self.y_min + y_range;
+        self.mark_dirty();
+    }
+
+    // Independent per point, which is what makes the parallel feature's
+    // row-at-a-time split safe.
+    fn escape_count(
+        &self,
+        c: Complex<f64>,
+        max_iterations: u16,
+        bailout_norm_sqr: f64,
+        track_derivative: bool,
+    ) -> Sample {
+        match self.precision() {
+            Precision::Standard => {
+                Self::escape_count_f64(c, max_iterations, bailout_norm_sqr, track_derivative)
+            }
+            Precision::High => self.escape_count_high_precision(
+                c,
+                max_iterations,
+                bailout_norm_sqr,
+                track_derivative,
+            ),
+        }
+    }
+
+    fn escape_count_f64(
+        c: Complex<f64>,
+        max_iterations: u16,
+        bailout_norm_sqr: f64,
+        track_derivative: bool,
+    ) -> Sample {
+        let mut z = Complex::new(0.0, 0.0);
+        let mut dz = Complex::new(0.0, 0.0);
+        let mut n = 0;
+
+        while z.norm_sqr() <= bailout_norm_sqr && n < max_iterations {
+            if track_derivative {
+                dz = Complex::new(2.0, 0.0) * z * dz + Complex::new(1.0, 0.0);
             }
+            z = z * z + c;
+            n += 1;
+        }
+
+        Sample {
+            iterations: n,
+            norm_sqr: z.norm_sqr(),
+            dz_norm_sqr: dz.norm_sqr(),
         }
+    }
 
-        // coloring https://en.wikipedia.org/wiki/Plotting_algorithms_for_the_Mandelbrot_set#Histogram_coloring
+    fn escape_count_high_precision(
+        &self,
+        c: Complex<f64>,
+        max_iterations: u16,
+        bailout_norm_sqr: f64,
+        track_derivative: bool,
+    ) -> Sample {
+        let precision_bits =
+            ((64.0 + self.zoom_depth() * 4.0).round() as usize).min(MAX_PRECISION_BITS);
+        let c = high_precision::Complex::from_f64(c.re, c.im, precision_bits);
+        let mut z = high_precision::Complex::zero(precision_bits);
+        let mut dz = high_precision::Complex::zero(precision_bits);
+        let mut n = 0;
+        let mut norm_sqr = z.norm_sqr_f64();
 
-        let mut histogram = vec![0; self.max_iterations as usize + 1];
-        for &count in &pixels {
-            if count < self.max_iterations {
-                histogram[count as usize] += 1;
+        while norm_sqr <= bailout_norm_sqr && n < max_iterations {
+            if track_derivative {
+                dz = dz.derivative_step(&z);
             }
+            z = z.square_add(&c);
+            n += 1;
+            norm_sqr = z.norm_sqr_f64();
         }
 
-        let mut total = 0;
-        for i in 0..histogram.len() {
-            total += histogram[i];
+        Sample {
+            iterations: n,
+            norm_sqr,
+            dz_norm_sqr: dz.norm_sqr_f64(),
         }
+    }
 
-        let mut brightness = vec![0.0; pixels.len()];
-        for (count, brightness) in zip(pixels, &mut brightness) {
-            if count == self.max_iterations {
+    // Only every `stride`-th pixel is evaluated; its sample is replicated
+    // across the rest of its block.
+    fn compute_row(&self, y: u32, grid: &Grid) -> Vec<Sample> {
+        let mut row = vec![Sample::default(); grid.width as usize];
+        let mut x = 0;
+        while x < grid.width {
+            let c = Complex::new(
+                self.x_min + x as f64 * grid.x_step,
+                self.y_min + y as f64 * grid.y_step,
+            );
+            let sample = self.escape_count(
+                c,
+                grid.max_iterations,
+                grid.bailout_norm_sqr,
+                grid.track_derivative,
+            );
+            for cell in &mut row[x as usize..((x + grid.stride).min(grid.width)) as usize] {
+                *cell = sample;
+            }
+            x += grid.stride;
+        }
+        row
+    }
+
+    // With the `parallel` feature enabled, grid rows are split across a
+    // rayon thread pool since each is independent; otherwise they are
+    // computed serially.
+    fn compute_pixels(&self, grid: &Grid) -> Vec<Sample> {
+        let grid_rows: Vec<u32> = (0..grid.height).step_by(grid.stride as usize).collect();
+
+        #[cfg(feature = "parallel")]
+        let rows = grid_rows.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let rows = grid_rows.iter();
+
+        let rows: Vec<(u32, Vec<Sample>)> = rows.map(|&y| (y, self.compute_row(y, grid))).collect();
+
+        let mut pixels = vec![Sample::default(); (grid.width * grid.height) as usize];
+        for (y, row) in rows {
+            for dest in y..(y + grid.stride).min(grid.height) {
+                let start = (dest * grid.width) as usize;
+                pixels[start..start + grid.width as usize].copy_from_slice(&row);
+            }
+        }
+        pixels
+    }
+
+    fn compute_colors(
+        &self,
+        samples: &[Sample],
+        max_iterations: u16,
+        pixel_step: f64,
+    ) -> Vec<Color> {
+        match self.color_scheme {
+            ColorScheme::Histogram => Self::compute_histogram_colors(samples, max_iterations),
+            ColorScheme::DistanceEstimate => samples
+                .iter()
+                .map(|&sample| color::color_for_distance(sample, max_iterations, pixel_step))
+                .collect(),
+            ColorScheme::SmoothBlue | ColorScheme::FireGradient | ColorScheme::HsvCycle => samples
+                .iter()
+                .map(|&sample| {
+                    if sample.iterations == max_iterations {
+                        Color::Rgb(0, 0, 0)
+                    } else {
+                        color::color_for_mu(
+                            color::smooth_iteration_count(sample),
+                            self.color_scheme,
+                        )
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    // coloring https://en.wikipedia.org/wiki/Plotting_algorithms_for_the_Mandelbrot_set#Histogram_coloring
+    fn compute_histogram_colors(samples: &[Sample], max_iterations: u16) -> Vec<Color> {
+        let mut histogram = vec![0; max_iterations as usize + 1];
+        for sample in samples {
+            if sample.iterations < max_iterations {
+                histogram[sample.iterations as usize] += 1;
+            }
+        }
+
+        let total: u32 = histogram.iter().sum();
+
+        let mut brightness = vec![0.0; samples.len()];
+        for (sample, brightness) in zip(samples, &mut brightness) {
+            if sample.iterations == max_iterations {
                 continue;
             }
-            for i in 0..count {
+            for i in 0..sample.iterations {
                 *brightness += histogram[i as usize] as f64 / total as f64;
             }
         }
 
+        brightness
+            .into_iter()
+            .map(|brightness| Color::Rgb(0, 0, (brightness * 255.0).floor() as u8))
+            .collect()
+    }
+
+    // Recomputes `colors` at the current stride, then halves the stride for
+    // next time. Returns true if another, finer pass is still pending.
+    fn refine(&mut self, area: Rect) -> bool {
+        if area != self.area {
+            self.area = area;
+            self.stride = INITIAL_STRIDE;
+        }
+
+        let width = area.width as u32;
+        let height = area.height as u32 * 2;
+        let max_iterations = self.effective_max_iterations();
+        let bailout_norm_sqr = self.color_scheme.bailout_norm_sqr();
+        let track_derivative = self.color_scheme.needs_derivative();
+        let x_step = (self.x_max - self.x_min) / width as f64;
+        let y_step = (self.y_max - self.y_min) / height as f64;
+        let pixels = self.compute_pixels(&Grid {
+            width,
+            height,
+            stride: self.stride,
+            x_step,
+            y_step,
+            max_iterations,
+            bailout_norm_sqr,
+            track_derivative,
+        });
+        self.colors = self.compute_colors(&pixels, max_iterations, x_step);
+
+        if self.stride > 1 {
+            self.stride /= 2;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Unlike the half-block terminal rendering, each output pixel is its
+    // own sample rather than sharing one with a vertical neighbour.
+    fn render_to_image(&self, width: u32, height: u32) -> image::RgbImage {
+        let max_iterations = self.effective_max_iterations();
+        let bailout_norm_sqr = self.color_scheme.bailout_norm_sqr();
+        let track_derivative = self.color_scheme.needs_derivative();
+        let x_step = (self.x_max - self.x_min) / width as f64;
+        let y_step = (self.y_max - self.y_min) / height as f64;
+        let pixels = self.compute_pixels(&Grid {
+            width,
+            height,
+            stride: 1,
+            x_step,
+            y_step,
+            max_iterations,
+            bailout_norm_sqr,
+            track_derivative,
+        });
+        let colors = self.compute_colors(&pixels, max_iterations, x_step);
+
+        let mut image = image::RgbImage::new(width, height);
+        for (pixel, &color) in zip(image.pixels_mut(), &colors) {
+            *pixel = image::Rgb(color::to_rgb(color));
+        }
+        image
+    }
+
+    fn to_view(&self) -> ViewState {
+        ViewState {
+            x_min: self.x_min,
+            x_max: self.x_max,
+            y_min: self.y_min,
+            y_max: self.y_max,
+            max_iterations: self.manual_iterations,
+        }
+    }
+
+    fn apply_view(&mut self, view: &ViewState) {
+        self.x_min = view.x_min;
+        self.x_max = view.x_max;
+        self.y_min = view.y_min;
+        self.y_max = view.y_max;
+        self.manual_iterations = view.max_iterations;
+        self.mark_dirty();
+    }
+}
+
+impl Widget for &Mandelbrot {
+    fn render(self, area: Rect, buf: &mut Buffer) {
         // iterate to draw a half block on each buffer cell
         for y in 0..area.height {
             for x in 0..area.width {
-                let top = brightness[(y * 2 * area.width + x) as usize];
-                let bottom = brightness[((y * 2 + 1) * area.width + x) as usize];
-
-                let fg = Color::Rgb(0, 0, (top * 255.0).floor() as u8);
-                let bg = Color::Rgb(0, 0, (bottom * 255.0).floor() as u8);
+                let fg = self.colors[(y * 2 * area.width + x) as usize];
+                let bg = self.colors[((y * 2 + 1) * area.width + x) as usize];
 
                 buf[(x, y)].set_fg(fg).set_bg(bg).set_symbol("â–€");
             }
@@ -0,0 +1,29 @@
+// Bookmarking interesting locations: the bounds and iteration budget
+// needed to reproduce a view, persisted as JSON so the list survives
+// between runs.
+
+use serde::{Deserialize, Serialize};
+
+const BOOKMARKS_FILE: &str = "mandelbrot_views.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewState {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+    pub max_iterations: Option<u16>,
+}
+
+pub fn load() -> Vec<ViewState> {
+    std::fs::read_to_string(BOOKMARKS_FILE)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(views: &[ViewState]) -> std::io::Result<()> {
+    let contents =
+        serde_json::to_string_pretty(views).expect("serializing a Vec<ViewState> cannot fail");
+    std::fs::write(BOOKMARKS_FILE, contents)
+}